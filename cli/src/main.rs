@@ -1,6 +1,10 @@
-use clap::Command;
+use clap::{Arg, Command};
+use std::error::Error;
 
-fn main() {
+use node::config::Settings;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
     let cli_app = Command::new("t-chain")
         .version("0.0.1")
         .author("Your Name")
@@ -8,18 +12,33 @@ fn main() {
         .subcommand(
             Command::new("start")
                 .about("Starts a t-chain daemon")
-                .subcommand(Command::new("node").about("Starts a t-chain node process")),
+                .subcommand(
+                    Command::new("node").about("Starts a t-chain node process").arg(
+                        Arg::new("config")
+                            .long("config")
+                            .value_name("FILE")
+                            .help("Path to a JSON node config file (see node::config::Settings)"),
+                    ),
+                ),
         );
 
     let matches = cli_app.get_matches();
 
     match matches.subcommand() {
         Some(("start", start_matches)) => match start_matches.subcommand() {
-            Some(("node", _)) => {
-                println!("Starting node");
+            Some(("node", node_matches)) => {
+                let settings = match node_matches.get_one::<String>("config") {
+                    Some(config_path) => Settings::from_file(config_path)?,
+                    None => Settings::default(),
+                };
+
+                println!("Starting node on chain '{}'", settings.chain_name);
+                node::run(settings).await?;
             }
             _ => unreachable!("Invariance violation detected"),
         },
         _ => unreachable!("Invariance violation detected"),
     }
+
+    Ok(())
 }
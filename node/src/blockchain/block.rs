@@ -0,0 +1,79 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::transaction::Transaction;
+
+/// A single block in the chain, including the proof-of-work fields needed
+/// to verify it without trusting the node that mined it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Block {
+    pub number: u64,
+    pub previous_hash: String,
+    pub nonce: u64,
+    pub timestamp: u64,
+    pub transactions: Vec<Transaction>,
+    pub hash: String,
+}
+
+impl Block {
+    pub const GENESIS_BLOCK_NUMBER: u64 = 1;
+
+    /// Builds an unmined block header. `hash` is left empty and `nonce` at
+    /// zero until [`Blockchain::mine`](crate::blockchain::Blockchain::mine)
+    /// grinds them.
+    pub fn new(
+        last_mined_block_number: Option<u64>,
+        previous_hash: String,
+        transactions: &Vec<Transaction>,
+    ) -> Self {
+        Self {
+            number: last_mined_block_number
+                .and_then(|last_mined_block_number| Some(last_mined_block_number + 1))
+                .unwrap_or(Self::GENESIS_BLOCK_NUMBER),
+            previous_hash,
+            nonce: 0,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system clock is after the unix epoch")
+                .as_secs(),
+            transactions: transactions.clone(),
+            hash: String::new(),
+        }
+    }
+
+    /// Recomputes the SHA-256 hash of the block header (everything but the
+    /// `hash` field itself). Used both while mining and while validating a
+    /// block someone else claims to have mined.
+    pub fn compute_hash(&self) -> String {
+        self.header_hash(&self.transactions_root())
+    }
+
+    /// SHA-256 digest of the block's serialized transactions, committing
+    /// to their contents without the hash itself depending on how many
+    /// there are.
+    pub fn transactions_root(&self) -> String {
+        let transactions_bytes = bincode::serialize(&self.transactions)
+            .expect("transactions are always serializable");
+        hex::encode(Sha256::digest(transactions_bytes))
+    }
+
+    /// Hashes the fixed-size block header (number, previous_hash, nonce,
+    /// timestamp, and a precomputed `transactions_root`) rather than the
+    /// full transaction list. Grinding `nonce` only touches this fixed-size
+    /// header, so mining cost no longer scales with how many transactions
+    /// a block carries.
+    pub fn header_hash(&self, transactions_root: &str) -> String {
+        let header = (
+            self.number,
+            &self.previous_hash,
+            self.nonce,
+            self.timestamp,
+            transactions_root,
+        );
+        let header_bytes =
+            bincode::serialize(&header).expect("block header is always serializable");
+        hex::encode(Sha256::digest(header_bytes))
+    }
+}
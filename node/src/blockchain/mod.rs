@@ -0,0 +1,287 @@
+mod block;
+mod store;
+
+pub use block::Block;
+
+use std::fmt;
+
+use num_bigint::BigUint;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::transaction::Transaction;
+use store::Store;
+
+/// Why a block was rejected by [`Blockchain::add_block`].
+#[derive(Debug)]
+pub enum BlockValidationError {
+    UnexpectedNumber { expected: u64, actual: u64 },
+    PreviousHashMismatch,
+    HashMismatch,
+    DifficultyNotMet,
+    Storage(String),
+}
+
+impl fmt::Display for BlockValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedNumber { expected, actual } => write!(
+                f,
+                "expected block number {expected}, got {actual}"
+            ),
+            Self::PreviousHashMismatch => {
+                write!(f, "previous_hash does not match the current chain tip")
+            }
+            Self::HashMismatch => write!(f, "claimed hash does not match its recomputed hash"),
+            Self::DifficultyNotMet => write!(f, "hash does not satisfy the difficulty target"),
+            Self::Storage(message) => write!(f, "failed to persist block: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for BlockValidationError {}
+
+/// A block's hash, read as a big-endian unsigned integer, must fall below
+/// this target for the block to count as mined. Higher `difficulty` shrinks
+/// the target, requiring more leading zero bits.
+fn target_for_difficulty(difficulty: u32) -> BigUint {
+    (BigUint::from(1u8) << 256u32) - BigUint::from(1u8) >> difficulty
+}
+
+fn hash_as_uint(hash: &str) -> BigUint {
+    BigUint::from_bytes_be(&hex::decode(hash).expect("hash is valid hex"))
+}
+
+pub struct Blockchain {
+    blocks: Mutex<Vec<Block>>,
+    difficulty: u32,
+    store: Store,
+}
+
+impl Blockchain {
+    pub const BLOCK_MINING_INTERVAL_MS: u64 = 10000;
+    pub const DEFAULT_DIFFICULTY: u32 = 20;
+
+    /// Opens (or creates) the SQLite-backed store at `db_path` and rebuilds
+    /// `last_block` from the highest block number found on disk, so a
+    /// restarted node picks up the chain where it left off instead of
+    /// starting empty. `db_path` comes from [`Settings`](crate::config::Settings),
+    /// so distinct networks (or multiple nodes sharing a directory) don't
+    /// collide on the same file.
+    pub async fn new(difficulty: u32, db_path: &str) -> Self {
+        let store = Store::open(db_path).expect("failed to open blockchain db");
+        let blocks = store
+            .load_blocks()
+            .await
+            .expect("failed to load persisted blocks");
+
+        Self {
+            blocks: Mutex::new(blocks),
+            difficulty,
+            store,
+        }
+    }
+
+    pub async fn get_last_mined_block_number(&self) -> Option<u64> {
+        let last_mined_block = self.get_last_mined_block().await;
+
+        last_mined_block.and_then(|block| Some(block.number))
+    }
+
+    pub async fn get_last_mined_block(&self) -> Option<Block> {
+        let blocks = self.blocks.lock().await;
+
+        blocks.last().cloned()
+    }
+
+    pub async fn blocks(&self) -> Vec<Block> {
+        self.blocks.lock().await.clone()
+    }
+
+    /// Blocks at or above `from_height`, for answering a peer's
+    /// `GetChain` request.
+    pub async fn get_blocks_from(&self, from_height: u64) -> Vec<Block> {
+        self.blocks
+            .lock()
+            .await
+            .iter()
+            .filter(|block| block.number >= from_height)
+            .cloned()
+            .collect()
+    }
+
+    /// Applies a sequence of blocks received from a peer's `ChainResponse`,
+    /// oldest first. Each block goes through [`Blockchain::add_block`] in
+    /// turn, which enforces number continuity, previous-hash linkage and
+    /// difficulty. The segment is not validated as a whole up front: the
+    /// first invalid block stops the rest from being applied, and the
+    /// blocks applied before it stay in the chain.
+    pub async fn apply_chain_segment(&self, blocks: Vec<Block>) -> Result<(), BlockValidationError> {
+        for block in blocks {
+            self.add_block(block).await?;
+        }
+        Ok(())
+    }
+
+    /// Grinds `nonce` until the block's hash satisfies the chain's
+    /// difficulty target, then appends it. If the tip moves out from under
+    /// us while grinding (a peer's block arrived via gossip in the
+    /// meantime), `add_block` rejects the stale block and we re-mine on
+    /// the new tip rather than panicking.
+    pub async fn mine(&self, transactions: &Vec<Transaction>) -> Block {
+        loop {
+            let last_block = self.get_last_mined_block().await;
+            let previous_hash = last_block
+                .as_ref()
+                .map(|block| block.hash.clone())
+                .unwrap_or_default();
+            let last_block_number = last_block.map(|block| block.number);
+
+            let mut block = Block::new(last_block_number, previous_hash, transactions);
+            let target = target_for_difficulty(self.difficulty);
+
+            // Computed once per block: grinding the nonce only re-hashes the
+            // fixed-size header below, so mining cost doesn't scale with how
+            // many transactions the block carries.
+            let transactions_root = block.transactions_root();
+
+            loop {
+                block.hash = block.header_hash(&transactions_root);
+                if hash_as_uint(&block.hash) < target {
+                    break;
+                }
+                block.nonce += 1;
+            }
+
+            match self.add_block(block.clone()).await {
+                Ok(()) => return block,
+                Err(error) => {
+                    warn!(
+                        "discarding mined block {}: {error}; re-mining on the current tip",
+                        block.number
+                    );
+                }
+            }
+        }
+    }
+
+    /// Validates `block` against the current chain tip and the difficulty
+    /// target before appending it. This is the integrity gate a block must
+    /// pass whether it was mined locally or received over gossipsub from a
+    /// peer.
+    pub async fn add_block(&self, block: Block) -> Result<(), BlockValidationError> {
+        let mut blocks = self.blocks.lock().await;
+        let last_block = blocks.last();
+
+        let expected_number = last_block
+            .map(|block| block.number + 1)
+            .unwrap_or(Block::GENESIS_BLOCK_NUMBER);
+        if block.number != expected_number {
+            return Err(self.reject(
+                &block,
+                BlockValidationError::UnexpectedNumber {
+                    expected: expected_number,
+                    actual: block.number,
+                },
+            ));
+        }
+
+        let expected_previous_hash = last_block
+            .map(|block| block.hash.clone())
+            .unwrap_or_default();
+        if block.previous_hash != expected_previous_hash {
+            return Err(self.reject(&block, BlockValidationError::PreviousHashMismatch));
+        }
+
+        if block.compute_hash() != block.hash {
+            return Err(self.reject(&block, BlockValidationError::HashMismatch));
+        }
+
+        if hash_as_uint(&block.hash) >= target_for_difficulty(self.difficulty) {
+            return Err(self.reject(&block, BlockValidationError::DifficultyNotMet));
+        }
+
+        self.store.insert_block(&block).await.map_err(|error| {
+            self.reject(&block, BlockValidationError::Storage(error.to_string()))
+        })?;
+
+        blocks.push(block);
+        Ok(())
+    }
+
+    fn reject(&self, block: &Block, error: BlockValidationError) -> BlockValidationError {
+        warn!("rejecting block {}: {error}", block.number);
+        error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_chain(difficulty: u32) -> Blockchain {
+        Blockchain {
+            blocks: Mutex::new(Vec::new()),
+            difficulty,
+            store: Store::open(":memory:").expect("in-memory sqlite db always opens"),
+        }
+    }
+
+    #[test]
+    fn target_shrinks_as_difficulty_grows() {
+        assert!(target_for_difficulty(20) < target_for_difficulty(1));
+    }
+
+    #[tokio::test]
+    async fn mine_produces_a_linked_chain() {
+        let chain = test_chain(1);
+
+        let first = chain.mine(&Vec::new()).await;
+        assert_eq!(first.number, Block::GENESIS_BLOCK_NUMBER);
+
+        let second = chain.mine(&Vec::new()).await;
+        assert_eq!(second.number, first.number + 1);
+        assert_eq!(second.previous_hash, first.hash);
+    }
+
+    #[tokio::test]
+    async fn add_block_rejects_wrong_number() {
+        let chain = test_chain(1);
+
+        let mut block = Block::new(None, String::new(), &Vec::new());
+        block.number = 5;
+        block.hash = block.compute_hash();
+
+        let error = chain.add_block(block).await.unwrap_err();
+        assert!(matches!(
+            error,
+            BlockValidationError::UnexpectedNumber {
+                expected: 1,
+                actual: 5
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn add_block_rejects_tampered_hash() {
+        let chain = test_chain(1);
+
+        let mut block = Block::new(None, String::new(), &Vec::new());
+        block.hash = block.compute_hash();
+        block.hash.push('0');
+
+        let error = chain.add_block(block).await.unwrap_err();
+        assert!(matches!(error, BlockValidationError::HashMismatch));
+    }
+
+    #[tokio::test]
+    async fn add_block_rejects_hash_above_difficulty_target() {
+        let chain = test_chain(255);
+
+        let mut block = Block::new(None, String::new(), &Vec::new());
+        block.hash = block.compute_hash();
+
+        let error = chain.add_block(block).await.unwrap_err();
+        assert!(matches!(error, BlockValidationError::DifficultyNotMet));
+    }
+}
@@ -0,0 +1,76 @@
+use tokio::sync::Mutex;
+
+use super::Block;
+
+/// SQLite-backed persistence for mined blocks, so chain state survives a
+/// node restart instead of living only in the in-memory `Vec<Block>`.
+pub struct Store {
+    connection: Mutex<sqlite::Connection>,
+}
+
+impl Store {
+    pub fn open(path: &str) -> sqlite::Result<Self> {
+        let connection = sqlite::open(path)?;
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS blocks (
+                number INTEGER PRIMARY KEY,
+                previous_hash TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                nonce INTEGER NOT NULL,
+                timestamp INTEGER NOT NULL,
+                transactions TEXT NOT NULL
+            )",
+        )?;
+
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// Writes an already-validated block through to disk.
+    pub async fn insert_block(&self, block: &Block) -> sqlite::Result<()> {
+        let connection = self.connection.lock().await;
+        let mut statement = connection.prepare(
+            "INSERT INTO blocks (number, previous_hash, hash, nonce, timestamp, transactions)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )?;
+        let transactions = serde_json::to_string(&block.transactions)
+            .expect("transactions are always serializable");
+
+        statement.bind((1, block.number as i64))?;
+        statement.bind((2, block.previous_hash.as_str()))?;
+        statement.bind((3, block.hash.as_str()))?;
+        statement.bind((4, block.nonce as i64))?;
+        statement.bind((5, block.timestamp as i64))?;
+        statement.bind((6, transactions.as_str()))?;
+
+        while let sqlite::State::Row = statement.next()? {}
+        Ok(())
+    }
+
+    /// Loads every persisted block, ordered oldest to newest, so the chain
+    /// can be rebuilt in memory on startup.
+    pub async fn load_blocks(&self) -> sqlite::Result<Vec<Block>> {
+        let connection = self.connection.lock().await;
+        let mut statement = connection.prepare(
+            "SELECT number, previous_hash, hash, nonce, timestamp, transactions
+             FROM blocks ORDER BY number ASC",
+        )?;
+
+        let mut blocks = Vec::new();
+        while let sqlite::State::Row = statement.next()? {
+            let transactions: String = statement.read(5)?;
+            blocks.push(Block {
+                number: statement.read::<i64, _>(0)? as u64,
+                previous_hash: statement.read(1)?,
+                hash: statement.read(2)?,
+                nonce: statement.read::<i64, _>(3)? as u64,
+                timestamp: statement.read::<i64, _>(4)? as u64,
+                transactions: serde_json::from_str(&transactions)
+                    .expect("stored transactions are always valid json"),
+            });
+        }
+
+        Ok(blocks)
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::blockchain::Blockchain;
+
+/// Node configuration loaded from a JSON file, so different networks and
+/// reproducible nodes don't require recompiling hardcoded constants.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub chain_name: String,
+    pub protocol_version: String,
+    pub listen_addresses: Vec<String>,
+    #[serde(default)]
+    pub bootstrap_peers: Vec<String>,
+    pub block_mining_interval_ms: u64,
+    pub difficulty: u32,
+    pub key_file_path: String,
+    pub db_path: String,
+}
+
+impl Settings {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let contents = fs::read_to_string(path).map_err(ConfigError::Io)?;
+        serde_json::from_str(&contents).map_err(ConfigError::Parse)
+    }
+
+    /// The gossipsub topic this node subscribes to. Deriving it from the
+    /// chain name and protocol version keeps distinct networks, and
+    /// incompatible protocol revisions of the same network, from
+    /// cross-talking on the same swarm.
+    pub fn topic_name(&self) -> String {
+        format!("{}/{}", self.chain_name, self.protocol_version)
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            chain_name: "t-chain-test-net".to_string(),
+            protocol_version: "1".to_string(),
+            listen_addresses: vec![
+                "/ip4/0.0.0.0/udp/0/quic-v1".to_string(),
+                "/ip4/0.0.0.0/tcp/0".to_string(),
+            ],
+            bootstrap_peers: Vec::new(),
+            block_mining_interval_ms: Blockchain::BLOCK_MINING_INTERVAL_MS,
+            difficulty: Blockchain::DEFAULT_DIFFICULTY,
+            key_file_path: "node.key".to_string(),
+            db_path: "blockchain.db".to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read config file: {error}"),
+            Self::Parse(error) => write!(f, "failed to parse config file: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
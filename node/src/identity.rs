@@ -0,0 +1,46 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use ed25519_dalek::SigningKey;
+use rand::rngs::OsRng;
+
+/// Loads this node's wallet signing key from `path`, generating and
+/// persisting a fresh one if the file doesn't exist yet. This is what
+/// makes a node's identity durable across restarts instead of a new
+/// keypair being minted every run.
+pub fn load_or_create(path: impl AsRef<Path>) -> Result<SigningKey, IdentityError> {
+    let path = path.as_ref();
+
+    match fs::read(path) {
+        Ok(bytes) => {
+            let bytes: [u8; 32] = bytes
+                .try_into()
+                .map_err(|_| IdentityError::InvalidKeyFile)?;
+            Ok(SigningKey::from_bytes(&bytes))
+        }
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            let signing_key = SigningKey::generate(&mut OsRng);
+            fs::write(path, signing_key.to_bytes()).map_err(IdentityError::Io)?;
+            Ok(signing_key)
+        }
+        Err(error) => Err(IdentityError::Io(error)),
+    }
+}
+
+#[derive(Debug)]
+pub enum IdentityError {
+    Io(std::io::Error),
+    InvalidKeyFile,
+}
+
+impl fmt::Display for IdentityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to access key file: {error}"),
+            Self::InvalidKeyFile => write!(f, "key file does not contain a 32-byte ed25519 key"),
+        }
+    }
+}
+
+impl std::error::Error for IdentityError {}
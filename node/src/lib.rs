@@ -0,0 +1,229 @@
+pub mod blockchain;
+pub mod config;
+pub mod identity;
+pub mod network;
+pub mod transaction;
+
+use ed25519_dalek::SigningKey;
+use futures::stream::StreamExt;
+use libp2p::{gossipsub, mdns, noise, swarm::NetworkBehaviour, swarm::SwarmEvent, tcp, yamux};
+use rand::rngs::OsRng;
+use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::{self, Duration as TokioDuration};
+use tokio::{io, io::AsyncBufReadExt, select};
+
+use blockchain::{Blockchain, BlockValidationError};
+use config::Settings;
+use network::NetworkMessage;
+use transaction::{Transaction, TransactionPool};
+
+#[derive(NetworkBehaviour)]
+struct MyBehaviour {
+    gossipsub: gossipsub::Behaviour,
+    mdns: mdns::tokio::Behaviour,
+}
+
+/// Starts a node under `settings`: brings up the swarm on the configured
+/// listen addresses, dials the configured bootstrap peers, subscribes to
+/// the chain's topic, and runs the mining/gossip event loop until the
+/// process exits.
+pub async fn run(settings: Settings) -> Result<(), Box<dyn Error>> {
+    let mut swarm = libp2p::SwarmBuilder::with_new_identity()
+        .with_tokio()
+        .with_tcp(
+            tcp::Config::default(),
+            noise::Config::new,
+            yamux::Config::default,
+        )?
+        .with_quic()
+        .with_behaviour(|key| {
+            let gossipsub_config = gossipsub::ConfigBuilder::default()
+                .heartbeat_interval(Duration::from_secs(10)) // This is set to aid debugging by not cluttering the log space
+                // .validation_mode(gossipsub::ValidationMode::Strict) // This sets the kind of message validation. The default is Strict (enforce message signing)
+                .build()
+                .map_err(|msg| io::Error::new(io::ErrorKind::Other, msg))?;
+
+            // build a gossipsub network behaviour
+            let gossipsub = gossipsub::Behaviour::new(
+                gossipsub::MessageAuthenticity::Signed(key.clone()),
+                gossipsub_config,
+            )?;
+
+            let mdns =
+                mdns::tokio::Behaviour::new(mdns::Config::default(), key.public().to_peer_id())?;
+            Ok(MyBehaviour { gossipsub, mdns })
+        })?
+        .with_swarm_config(|c| c.with_idle_connection_timeout(Duration::from_secs(60)))
+        .build();
+
+    let topic = gossipsub::IdentTopic::new(settings.topic_name());
+    swarm.behaviour_mut().gossipsub.subscribe(&topic)?;
+
+    let mut stdin = io::BufReader::new(io::stdin()).lines();
+
+    for address in &settings.listen_addresses {
+        swarm.listen_on(address.parse()?)?;
+    }
+    for peer in &settings.bootstrap_peers {
+        swarm.dial(peer.parse::<libp2p::Multiaddr>()?)?;
+    }
+
+    println!("Available commands: 'ADD_TRANSACTION', 'FETCH_BLOCKCHAIN'");
+
+    let blockchain = Arc::new(Blockchain::new(settings.difficulty, &settings.db_path).await);
+    let transaction_pool = Arc::new(TransactionPool::new());
+
+    // This node's wallet identity, persisted at `settings.key_file_path` so
+    // it survives restarts instead of being regenerated every run.
+    let signing_key = identity::load_or_create(&settings.key_file_path)?;
+    // Stands in for a counterparty until there's a way to address a real peer.
+    let demo_recipient = SigningKey::generate(&mut OsRng).verifying_key();
+    let next_nonce = Arc::new(Mutex::new(0u64));
+
+    // The mining task runs outside the swarm-owning select! loop, so newly
+    // mined blocks are handed back over a channel for the loop to gossip.
+    let (mined_block_tx, mut mined_block_rx) = mpsc::unbounded_channel::<NetworkMessage>();
+
+    {
+        let blockchain = blockchain.clone();
+        let transaction_pool = transaction_pool.clone();
+        let block_mining_interval_ms = settings.block_mining_interval_ms;
+
+        tokio::spawn(async move {
+            const BLOCK_TRANSACTION_LIMIT: usize = 500;
+
+            let mut new_block_interval =
+                time::interval(TokioDuration::from_millis(block_mining_interval_ms));
+
+            loop {
+                new_block_interval.tick().await;
+
+                let ready_transactions = transaction_pool
+                    .get_ready_transactions(BLOCK_TRANSACTION_LIMIT)
+                    .await;
+                if !ready_transactions.is_empty() {
+                    let mined_block = blockchain.mine(&ready_transactions).await;
+                    println!(
+                        "Mined block {} with hash {}",
+                        mined_block.number, mined_block.hash
+                    );
+                    transaction_pool.remove_mined(&ready_transactions).await;
+                    let _ = mined_block_tx.send(NetworkMessage::NewBlock(mined_block));
+                }
+            }
+        });
+    }
+
+    loop {
+        let transaction_pool = transaction_pool.clone();
+        let signing_key = signing_key.clone();
+        let next_nonce = next_nonce.clone();
+
+        let sign_and_pool_transaction = async move {
+            let mut nonce = next_nonce.lock().await;
+            let transaction = Transaction::sign(&signing_key, demo_recipient, 1, *nonce);
+
+            match transaction_pool.add(transaction.clone()).await {
+                Ok(()) => {
+                    *nonce += 1;
+                    println!("Transaction Added to MemPool/TransactionPool");
+                    Some(transaction)
+                }
+                Err(error) => {
+                    eprintln!("Rejected transaction: {error}");
+                    None
+                }
+            }
+        };
+        select! {
+            Ok(Some(line)) = stdin.next_line() => {
+                match line.as_str() {
+                    "ADD_TRANSACTION" => {
+                        if let Some(transaction) = sign_and_pool_transaction.await {
+                            let message = NetworkMessage::NewTransaction(transaction);
+                            if let Err(e) = swarm.behaviour_mut().gossipsub.publish(topic.clone(), message.encode()) {
+                                println!("Publish error: {e:?}");
+                            }
+                        }
+                    },
+                    "FETCH_BLOCKCHAIN" =>  {
+                        println!("Blockchain: {:?}", blockchain.blocks().await);
+                    }
+                    _ =>  eprintln!("Invalid command")
+                }
+
+            }
+            Some(message) = mined_block_rx.recv() => {
+                if let Err(e) = swarm.behaviour_mut().gossipsub.publish(topic.clone(), message.encode()) {
+                    println!("Publish error: {e:?}");
+                }
+            }
+            event = swarm.select_next_some() => match event {
+                SwarmEvent::Behaviour(MyBehaviourEvent::Mdns(mdns::Event::Discovered(list))) => {
+                    for (peer_id, _multiaddr) in list {
+                        println!("mDNS discovered a new peer: {peer_id}");
+                        swarm.behaviour_mut().gossipsub.add_explicit_peer(&peer_id);
+                    }
+                },
+                SwarmEvent::Behaviour(MyBehaviourEvent::Mdns(mdns::Event::Expired(list))) => {
+                    for (peer_id, _multiaddr) in list {
+                        println!("mDNS discover peer has expired: {peer_id}");
+                        swarm.behaviour_mut().gossipsub.remove_explicit_peer(&peer_id);
+                    }
+                },
+                SwarmEvent::Behaviour(MyBehaviourEvent::Gossipsub(gossipsub::Event::Message {
+                    propagation_source: peer_id,
+                    message_id: id,
+                    message,
+                })) => {
+                    match NetworkMessage::decode(&message.data) {
+                        Ok(NetworkMessage::NewTransaction(transaction)) => {
+                            if let Err(error) = transaction_pool.add(transaction).await {
+                                eprintln!("Rejected transaction from peer {peer_id}: {error}");
+                            }
+                        }
+                        Ok(NetworkMessage::NewBlock(block)) => {
+                            let transactions = block.transactions.clone();
+                            match blockchain.add_block(block).await {
+                                Ok(()) => {
+                                    transaction_pool.remove_mined(&transactions).await;
+                                }
+                                Err(BlockValidationError::UnexpectedNumber { expected, actual })
+                                    if actual > expected =>
+                                {
+                                    let request = NetworkMessage::GetChain { from_height: expected };
+                                    if let Err(e) = swarm.behaviour_mut().gossipsub.publish(topic.clone(), request.encode()) {
+                                        println!("Publish error: {e:?}");
+                                    }
+                                }
+                                Err(error) => {
+                                    eprintln!("Rejected block from peer {peer_id}: {error}");
+                                }
+                            }
+                        }
+                        Ok(NetworkMessage::GetChain { from_height }) => {
+                            let response =
+                                NetworkMessage::ChainResponse(blockchain.get_blocks_from(from_height).await);
+                            if let Err(e) = swarm.behaviour_mut().gossipsub.publish(topic.clone(), response.encode()) {
+                                println!("Publish error: {e:?}");
+                            }
+                        }
+                        Ok(NetworkMessage::ChainResponse(blocks)) => {
+                            if let Err(error) = blockchain.apply_chain_segment(blocks).await {
+                                eprintln!("Rejected chain segment from peer {peer_id}: {error}");
+                            }
+                        }
+                        Err(_) => eprintln!("Invalid NODE message from peer: {peer_id} with id: {id}"),
+                    }
+                },
+                SwarmEvent::NewListenAddr { address, .. } => {
+                    println!("Local node is listening on {address}");
+                }
+                _ => {}
+            }
+        }
+    }
+}
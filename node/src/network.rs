@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use crate::blockchain::Block;
+use crate::transaction::Transaction;
+
+/// The wire format gossiped between nodes on the chain's topic. Replaces
+/// the old single hardcoded `"ADD_TRANSACTION"` string with a real,
+/// typed protocol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NetworkMessage {
+    NewTransaction(Transaction),
+    NewBlock(Block),
+    GetChain { from_height: u64 },
+    ChainResponse(Vec<Block>),
+}
+
+impl NetworkMessage {
+    pub fn encode(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("network message is always serializable")
+    }
+
+    pub fn decode(bytes: &[u8]) -> bincode::Result<Self> {
+        bincode::deserialize(bytes)
+    }
+}
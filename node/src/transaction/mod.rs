@@ -0,0 +1,400 @@
+mod transaction;
+
+pub use transaction::{Transaction, TransactionValidationError};
+
+use std::collections::{BTreeMap, HashMap};
+
+use ed25519_dalek::VerifyingKey;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// `VerifyingKey` doesn't implement `Hash`, so senders are indexed by their
+/// raw compressed point bytes instead.
+type SenderId = [u8; 32];
+
+/// A sender's pending transactions, ordered by nonce. `next_nonce` is the
+/// nonce this sender must submit next for a transaction to be "ready";
+/// anything queued past a gap is held as a future transaction until the
+/// gap is filled.
+#[derive(Default)]
+struct SenderQueue {
+    next_nonce: u64,
+    queued: BTreeMap<u64, Transaction>,
+    penalized: bool,
+}
+
+impl SenderQueue {
+    fn ready_len(&self) -> usize {
+        let mut nonce = self.next_nonce;
+        let mut count = 0;
+        while self.queued.contains_key(&nonce) {
+            count += 1;
+            nonce += 1;
+        }
+        count
+    }
+
+    fn ready_transactions(&self) -> Vec<&Transaction> {
+        let mut nonce = self.next_nonce;
+        let mut ready = Vec::new();
+        while let Some(transaction) = self.queued.get(&nonce) {
+            ready.push(transaction);
+            nonce += 1;
+        }
+        ready
+    }
+}
+
+/// Ranks a transaction for mining/eviction purposes. There's no separate
+/// fee yet, so `amount` doubles as the score; a penalized sender's
+/// transactions always sort last.
+fn score(transaction: &Transaction, penalized: bool) -> u64 {
+    if penalized {
+        0
+    } else {
+        transaction.amount
+    }
+}
+
+/// Removes the worst eviction candidate from `queue`, if one exists, to
+/// make room for a new arrival. Future (not-yet-ready) transactions are
+/// preferred victims since they can't be mined yet; only when none remain
+/// does a ready transaction get evicted too, so the bound holds against
+/// the total queued count and not just the future backlog. Among ready
+/// transactions the tie-break favors evicting the highest nonce, since
+/// evicting a lower one would only turn its still-queued successors into
+/// future transactions as well.
+fn evict_lowest_scored(queue: &mut SenderQueue) {
+    let ready_upper = queue.next_nonce + queue.ready_len() as u64;
+
+    let future_victim = queue
+        .queued
+        .iter()
+        .filter(|(&nonce, _)| nonce >= ready_upper)
+        .min_by_key(|(_, transaction)| score(transaction, queue.penalized))
+        .map(|(&nonce, _)| nonce);
+
+    let victim = future_victim.or_else(|| {
+        queue
+            .queued
+            .iter()
+            .filter(|(&nonce, _)| nonce < ready_upper)
+            .min_by_key(|(&nonce, transaction)| {
+                (score(transaction, queue.penalized), std::cmp::Reverse(nonce))
+            })
+            .map(|(&nonce, _)| nonce)
+    });
+
+    if let Some(nonce) = victim {
+        queue.queued.remove(&nonce);
+    }
+}
+
+/// Same as [`evict_lowest_scored`] but scans every sender's queue to free
+/// a slot against the pool's global capacity.
+fn evict_lowest_scored_globally(senders: &mut HashMap<SenderId, SenderQueue>) {
+    let mut future_victim: Option<(SenderId, u64, u64)> = None;
+    let mut ready_victim: Option<(SenderId, u64, (u64, std::cmp::Reverse<u64>))> = None;
+
+    for (sender, queue) in senders.iter() {
+        let ready_upper = queue.next_nonce + queue.ready_len() as u64;
+
+        for (&nonce, transaction) in queue.queued.iter() {
+            let candidate_score = score(transaction, queue.penalized);
+
+            if nonce >= ready_upper {
+                let is_new_low = match &future_victim {
+                    Some((_, _, best)) => candidate_score < *best,
+                    None => true,
+                };
+                if is_new_low {
+                    future_victim = Some((*sender, nonce, candidate_score));
+                }
+            } else {
+                let key = (candidate_score, std::cmp::Reverse(nonce));
+                let is_new_low = match &ready_victim {
+                    Some((_, _, best)) => key < *best,
+                    None => true,
+                };
+                if is_new_low {
+                    ready_victim = Some((*sender, nonce, key));
+                }
+            }
+        }
+    }
+
+    let victim = future_victim
+        .map(|(sender, nonce, _)| (sender, nonce))
+        .or_else(|| ready_victim.map(|(sender, nonce, _)| (sender, nonce)));
+
+    if let Some((sender, nonce)) = victim {
+        if let Some(queue) = senders.get_mut(&sender) {
+            queue.queued.remove(&nonce);
+        }
+    }
+}
+
+/// A bounded, per-sender-indexed transaction pool. Transactions are ranked
+/// by score rather than drained in arrival order, so the miner always
+/// pulls the highest-value ready set instead of whatever happened to
+/// arrive first.
+pub struct TransactionPool {
+    senders: Mutex<HashMap<SenderId, SenderQueue>>,
+    capacity: usize,
+    per_sender_cap: usize,
+}
+
+impl TransactionPool {
+    pub const DEFAULT_CAPACITY: usize = 10_000;
+    /// Each sender may occupy at most this fraction of total capacity, so
+    /// one busy sender can't starve everyone else's transactions out.
+    pub const PER_SENDER_CAP_RATIO: f64 = 0.1;
+
+    pub fn new() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let per_sender_cap = ((capacity as f64 * Self::PER_SENDER_CAP_RATIO).ceil() as usize).max(1);
+
+        Self {
+            senders: Mutex::new(HashMap::new()),
+            capacity,
+            per_sender_cap,
+        }
+    }
+
+    /// Rejects transactions whose signature doesn't verify against their
+    /// claimed sender, then slots the transaction into its sender's queue.
+    /// If the sender is at its cap, or the pool goes over its capacity, the
+    /// lowest-scored queued transaction is evicted (future ones first, then
+    /// ready ones); the new arrival's own score is not compared.
+    pub async fn add(&self, transaction: Transaction) -> Result<(), TransactionValidationError> {
+        if !transaction.verify() {
+            warn!(
+                "rejecting transaction from {:?}: signature verification failed",
+                transaction.from
+            );
+            self.penalize(&transaction.from).await;
+            return Err(TransactionValidationError::InvalidSignature);
+        }
+
+        let mut senders = self.senders.lock().await;
+
+        let queue = senders.entry(transaction.from.to_bytes()).or_default();
+        if queue.queued.len() >= self.per_sender_cap {
+            evict_lowest_scored(queue);
+        }
+        queue.queued.insert(transaction.nonce, transaction);
+
+        let total_pending: usize = senders.values().map(|queue| queue.queued.len()).sum();
+        if total_pending > self.capacity {
+            evict_lowest_scored_globally(&mut senders);
+        }
+
+        Ok(())
+    }
+
+    /// Returns up to `limit` ready transactions for the miner to fill a
+    /// block with. Senders are ranked by the score of their next (lowest
+    /// nonce) ready transaction, highest first, but each sender's ready
+    /// transactions are always emitted as a contiguous run starting from
+    /// their next expected nonce — a higher nonce is never emitted ahead
+    /// of, or without, its predecessors, even when `limit` cuts a sender's
+    /// run short.
+    pub async fn get_ready_transactions(&self, limit: usize) -> Vec<Transaction> {
+        let senders = self.senders.lock().await;
+
+        let mut ranked: Vec<(u64, Vec<Transaction>)> = senders
+            .values()
+            .filter_map(|queue| {
+                let ready: Vec<Transaction> =
+                    queue.ready_transactions().into_iter().cloned().collect();
+                let head_score = score(ready.first()?, queue.penalized);
+                Some((head_score, ready))
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| b.0.cmp(&a.0));
+
+        let mut result = Vec::new();
+        for (_, ready) in ranked {
+            if result.len() >= limit {
+                break;
+            }
+            let take = (limit - result.len()).min(ready.len());
+            result.extend(ready.into_iter().take(take));
+        }
+        result
+    }
+
+    /// Drops only the transactions that were actually mined, advancing
+    /// each sender's expected next nonce accordingly, instead of clearing
+    /// the whole pool. A sender that lands a transaction on-chain has
+    /// demonstrated its queue is valid again, so this also lifts any prior
+    /// penalty rather than leaving it demoted forever.
+    pub async fn remove_mined(&self, mined: &[Transaction]) {
+        let mut nonces_by_sender: HashMap<SenderId, Vec<u64>> = HashMap::new();
+        for transaction in mined {
+            nonces_by_sender
+                .entry(transaction.from.to_bytes())
+                .or_default()
+                .push(transaction.nonce);
+        }
+
+        let mut senders = self.senders.lock().await;
+        for (sender, mut nonces) in nonces_by_sender {
+            let Some(queue) = senders.get_mut(&sender) else {
+                continue;
+            };
+
+            nonces.sort_unstable();
+            for nonce in nonces {
+                queue.queued.remove(&nonce);
+                if nonce == queue.next_nonce {
+                    queue.next_nonce += 1;
+                }
+            }
+            queue.penalized = false;
+        }
+    }
+
+    /// Demotes every queued transaction from `sender` to the bottom of the
+    /// ranking after one of their transactions is found invalid (e.g. a
+    /// transaction whose signature fails to verify in [`Self::add`]).
+    pub async fn penalize(&self, sender: &VerifyingKey) {
+        let mut senders = self.senders.lock().await;
+        if let Some(queue) = senders.get_mut(&sender.to_bytes()) {
+            queue.penalized = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+    use rand::rngs::OsRng;
+
+    fn sender() -> SigningKey {
+        SigningKey::generate(&mut OsRng)
+    }
+
+    fn recipient() -> VerifyingKey {
+        SigningKey::generate(&mut OsRng).verifying_key()
+    }
+
+    #[tokio::test]
+    async fn ready_transactions_keep_a_senders_nonces_in_order() {
+        let pool = TransactionPool::new();
+        let alice = sender();
+        let to = recipient();
+
+        // Out of order amounts (5, 100, 3) must still come back as nonce 0, 1, 2.
+        pool.add(Transaction::sign(&alice, to, 5, 0)).await.unwrap();
+        pool.add(Transaction::sign(&alice, to, 100, 1)).await.unwrap();
+        pool.add(Transaction::sign(&alice, to, 3, 2)).await.unwrap();
+
+        let ready = pool.get_ready_transactions(1).await;
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].nonce, 0);
+
+        let ready = pool.get_ready_transactions(10).await;
+        let nonces: Vec<u64> = ready.iter().map(|transaction| transaction.nonce).collect();
+        assert_eq!(nonces, vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn ready_transactions_rank_senders_by_head_score_without_reordering_nonces() {
+        let pool = TransactionPool::new();
+        let to = recipient();
+
+        let alice = sender();
+        pool.add(Transaction::sign(&alice, to, 1, 0)).await.unwrap();
+        pool.add(Transaction::sign(&alice, to, 1, 1)).await.unwrap();
+
+        let bob = sender();
+        pool.add(Transaction::sign(&bob, to, 50, 0)).await.unwrap();
+
+        // Bob's single ready transaction outscores Alice's, so it comes first,
+        // but Alice's two transactions still appear together and in order.
+        let ready = pool.get_ready_transactions(10).await;
+        assert_eq!(ready[0].from, bob.verifying_key());
+        assert_eq!(ready[1].nonce, 0);
+        assert_eq!(ready[2].nonce, 1);
+    }
+
+    #[tokio::test]
+    async fn future_transactions_are_not_ready() {
+        let pool = TransactionPool::new();
+        let alice = sender();
+        let to = recipient();
+
+        // Nonce 1 arrives before nonce 0: it's a future transaction, not ready.
+        pool.add(Transaction::sign(&alice, to, 1, 1)).await.unwrap();
+
+        let ready = pool.get_ready_transactions(10).await;
+        assert!(ready.is_empty());
+    }
+
+    #[tokio::test]
+    async fn per_sender_cap_evicts_lowest_scored_future_transaction() {
+        let pool = TransactionPool::with_capacity(10);
+        let alice = sender();
+        let to = recipient();
+
+        // Capacity 10 => per-sender cap is 1, so each arrival evicts the one
+        // queued (future) transaction: nonce 5 goes when 6 arrives, and 6
+        // goes when 7 arrives.
+        pool.add(Transaction::sign(&alice, to, 1, 5)).await.unwrap();
+        pool.add(Transaction::sign(&alice, to, 1, 6)).await.unwrap();
+        pool.add(Transaction::sign(&alice, to, 100, 7)).await.unwrap();
+
+        let senders = pool.senders.lock().await;
+        let queue = &senders[&alice.verifying_key().to_bytes()];
+        assert_eq!(queue.queued.len(), 1);
+        assert!(queue.queued.contains_key(&7));
+    }
+
+    #[tokio::test]
+    async fn per_sender_cap_evicts_a_ready_transaction_when_no_future_ones_exist() {
+        // Capacity 30 => per-sender cap is 3.
+        let pool = TransactionPool::with_capacity(30);
+        let alice = sender();
+        let to = recipient();
+
+        // Three contiguous nonces from 0 are all ready, leaving no future
+        // transaction to evict. The cap must still hold by falling back to
+        // evicting a ready one, preferring the highest nonce among ties so
+        // the surviving prefix stays contiguous from the next expected nonce.
+        pool.add(Transaction::sign(&alice, to, 1, 0)).await.unwrap();
+        pool.add(Transaction::sign(&alice, to, 1, 1)).await.unwrap();
+        pool.add(Transaction::sign(&alice, to, 1, 2)).await.unwrap();
+        pool.add(Transaction::sign(&alice, to, 100, 3)).await.unwrap();
+
+        let senders = pool.senders.lock().await;
+        let queue = &senders[&alice.verifying_key().to_bytes()];
+        assert_eq!(queue.queued.len(), 3);
+        assert!(queue.queued.contains_key(&0));
+        assert!(queue.queued.contains_key(&1));
+        assert!(!queue.queued.contains_key(&2));
+        assert!(queue.queued.contains_key(&3));
+    }
+
+    #[tokio::test]
+    async fn remove_mined_advances_nonce_and_clears_penalty() {
+        let pool = TransactionPool::new();
+        let alice = sender();
+        let to = recipient();
+
+        let transaction = Transaction::sign(&alice, to, 1, 0);
+        pool.add(transaction.clone()).await.unwrap();
+        pool.penalize(&alice.verifying_key()).await;
+        pool.remove_mined(&[transaction]).await;
+
+        let senders = pool.senders.lock().await;
+        let queue = &senders[&alice.verifying_key().to_bytes()];
+        assert_eq!(queue.next_nonce, 1);
+        assert!(!queue.penalized);
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// A tamper-evident transfer, authenticated by the sender's ed25519
+/// signature over its own fields.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Transaction {
+    pub from: VerifyingKey,
+    pub to: VerifyingKey,
+    pub amount: u64,
+    pub nonce: u64,
+    pub signature: Signature,
+}
+
+impl Transaction {
+    /// Builds and signs a transaction on behalf of `signing_key`. `from` is
+    /// always derived from the signing key, never taken as input, so a
+    /// transaction can't claim to be sent by someone it wasn't signed by.
+    pub fn sign(signing_key: &SigningKey, to: VerifyingKey, amount: u64, nonce: u64) -> Self {
+        let from = signing_key.verifying_key();
+        let signature = signing_key.sign(&Self::canonical_message(&from, &to, amount, nonce));
+
+        Self {
+            from,
+            to,
+            amount,
+            nonce,
+            signature,
+        }
+    }
+
+    /// Checks that `signature` covers this transaction's fields and was
+    /// produced by `from`.
+    pub fn verify(&self) -> bool {
+        let message = Self::canonical_message(&self.from, &self.to, self.amount, self.nonce);
+
+        self.from.verify(&message, &self.signature).is_ok()
+    }
+
+    fn canonical_message(from: &VerifyingKey, to: &VerifyingKey, amount: u64, nonce: u64) -> Vec<u8> {
+        bincode::serialize(&(from.as_bytes(), to.as_bytes(), amount, nonce))
+            .expect("transaction fields are always serializable")
+    }
+}
+
+#[derive(Debug)]
+pub enum TransactionValidationError {
+    InvalidSignature,
+}
+
+impl fmt::Display for TransactionValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSignature => write!(f, "signature does not verify against its sender"),
+        }
+    }
+}
+
+impl std::error::Error for TransactionValidationError {}